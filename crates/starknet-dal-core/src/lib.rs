@@ -8,6 +8,85 @@
 //! 2. Users who want to build a custom data availability layer for Starknet that 1. can use
 //!    in their own projects with no added modifications.
 
-/// Represents a data availability layer responsible for providing data to the Starknet
-/// network without relying (necessarily) on a local database.
-pub trait DataAvailabilityLayer {}
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use core::{fmt, future::Future};
+
+/// The size, in bytes, of the chunks that [`DataAvailabilityLayer::put_chunked`] splits its
+/// input into.
+pub const CHUNK_SIZE: usize = 1 << 20; // 1 MiB
+
+/// A content-addressed identifier for a blob of data stored in a [`DataAvailabilityLayer`].
+///
+/// Two identical blobs always hash to the same [`ContentId`], which is what allows
+/// implementations to deduplicate storage: uploading a chunk that is already known is a no-op.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ContentId([u8; 32]);
+
+impl ContentId {
+    /// Computes the [`ContentId`] of `data`.
+    pub fn of(data: &[u8]) -> Self {
+        Self(*blake3::hash(data).as_bytes())
+    }
+
+    /// Returns the raw bytes of this identifier.
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+}
+
+impl fmt::Display for ContentId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for byte in self.0 {
+            write!(f, "{byte:02x}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Lists the [`ContentId`] of every chunk a blob was split into by
+/// [`DataAvailabilityLayer::put_chunked`], in order.
+#[derive(Debug, Clone)]
+pub struct Manifest {
+    pub chunks: Vec<ContentId>,
+}
+
+/// Represents a data availability layer responsible for publishing and retrieving the large
+/// `trace`/`memory` blobs and public-memory pages carried in a [`ProofRequest`], without relying
+/// (necessarily) on a local database.
+///
+/// [`ProofRequest`]: https://docs.rs/starknet-prove-core/*/starknet_prove_core/struct.ProofRequest.html
+pub trait DataAvailabilityLayer {
+    /// An error that might occur while publishing or retrieving data.
+    type Err;
+
+    /// Stores `data`, returning its content-addressed identifier.
+    ///
+    /// Storing bytes that are already known to the layer is expected to be a cheap no-op.
+    fn put(&self, data: &[u8]) -> impl Send + Future<Output = Result<ContentId, Self::Err>>;
+
+    /// Retrieves the data previously stored under `id`.
+    fn get(&self, id: &ContentId) -> impl Send + Future<Output = Result<Vec<u8>, Self::Err>>;
+
+    /// Splits `data` into [`CHUNK_SIZE`]-sized chunks, stores each of them with
+    /// [`put`](Self::put), and returns the resulting [`Manifest`].
+    ///
+    /// Re-uploading a blob that only changed slightly only actually stores the chunks that
+    /// differ, since unchanged chunks hash to [`ContentId`]s that [`put`](Self::put) already
+    /// knows about.
+    fn put_chunked(&self, data: &[u8]) -> impl Send + Future<Output = Result<Manifest, Self::Err>>
+    where
+        Self: Sync,
+    {
+        async {
+            let mut chunks = Vec::new();
+            for chunk in data.chunks(CHUNK_SIZE) {
+                chunks.push(self.put(chunk).await?);
+            }
+            Ok(Manifest { chunks })
+        }
+    }
+}
@@ -0,0 +1,132 @@
+//! A filesystem-backed implementation of [`DataAvailabilityLayer`].
+//!
+//! Chunks are stored as individual files named after their [`ContentId`], which makes storage
+//! naturally content-addressed and deduplicating: writing a chunk that is already on disk is a
+//! no-op.
+
+use std::{io, path::PathBuf};
+
+use starknet_dal_core::{ContentId, DataAvailabilityLayer};
+
+/// An error that can be produced by [`FsDataAvailabilityLayer`].
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// A system-level error occured while reading or writing a chunk.
+    #[error("{0}")]
+    Io(
+        #[source]
+        #[from]
+        io::Error,
+    ),
+}
+
+/// The compression codec applied to a chunk before it is written to disk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Compression {
+    /// Chunks are stored uncompressed.
+    #[default]
+    None,
+    /// Chunks are compressed with zstd.
+    #[cfg(feature = "zstd")]
+    Zstd,
+    /// Chunks are compressed with bzip2.
+    #[cfg(feature = "bzip2")]
+    Bzip2,
+}
+
+/// The configuration passed to [`FsDataAvailabilityLayer`] to configure its behavior.
+#[derive(Debug, Clone)]
+pub struct FsConfig {
+    /// The directory in which chunks are stored, one file per [`ContentId`].
+    ///
+    /// This directory is created on first use if it does not already exist.
+    pub root: PathBuf,
+    /// The compression codec applied to a chunk's bytes before it is written to disk.
+    pub compression: Compression,
+}
+
+/// Stores chunks as individual files on the local filesystem, named after their [`ContentId`].
+///
+/// This type implements the [`DataAvailabilityLayer`] trait.
+#[derive(Debug, Clone)]
+pub struct FsDataAvailabilityLayer {
+    root: PathBuf,
+    compression: Compression,
+}
+
+impl FsDataAvailabilityLayer {
+    /// Creates a new [`FsDataAvailabilityLayer`] instance from the provided configuration.
+    pub fn new(config: FsConfig) -> Self {
+        Self {
+            root: config.root,
+            compression: config.compression,
+        }
+    }
+
+    /// Returns the path at which the chunk identified by `id` is (or would be) stored.
+    fn path_of(&self, id: &ContentId) -> PathBuf {
+        self.root.join(id.to_string())
+    }
+}
+
+impl DataAvailabilityLayer for FsDataAvailabilityLayer {
+    type Err = Error;
+
+    async fn put(&self, data: &[u8]) -> Result<ContentId, Error> {
+        let id = ContentId::of(data);
+        let path = self.path_of(&id);
+
+        // Identical bytes always hash to the same id, so if the file is already there, there's
+        // nothing left to do.
+        if tokio::fs::try_exists(&path).await? {
+            return Ok(id);
+        }
+
+        let encoded = compress(self.compression, data)?;
+        tokio::fs::create_dir_all(&self.root).await?;
+        tokio::fs::write(path, encoded).await?;
+
+        Ok(id)
+    }
+
+    async fn get(&self, id: &ContentId) -> Result<Vec<u8>, Error> {
+        let encoded = tokio::fs::read(self.path_of(id)).await?;
+        decompress(self.compression, &encoded)
+    }
+}
+
+/// Compresses `data` according to `compression`.
+fn compress(compression: Compression, data: &[u8]) -> Result<Vec<u8>, Error> {
+    match compression {
+        Compression::None => Ok(data.to_vec()),
+        #[cfg(feature = "zstd")]
+        Compression::Zstd => Ok(zstd::stream::encode_all(data, 0)?),
+        #[cfg(feature = "bzip2")]
+        Compression::Bzip2 => {
+            use std::io::Write;
+
+            let mut encoder =
+                bzip2::write::BzEncoder::new(Vec::new(), bzip2::Compression::default());
+            encoder.write_all(data)?;
+            Ok(encoder.finish()?)
+        }
+    }
+}
+
+/// Reverses [`compress`].
+fn decompress(compression: Compression, data: &[u8]) -> Result<Vec<u8>, Error> {
+    match compression {
+        Compression::None => Ok(data.to_vec()),
+        #[cfg(feature = "zstd")]
+        Compression::Zstd => Ok(zstd::stream::decode_all(data)?),
+        #[cfg(feature = "bzip2")]
+        Compression::Bzip2 => {
+            use std::io::Read;
+
+            let mut decoder = bzip2::read::BzDecoder::new(data);
+            let mut out = Vec::new();
+            decoder.read_to_end(&mut out)?;
+            Ok(out)
+        }
+    }
+}
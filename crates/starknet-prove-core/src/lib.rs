@@ -1,5 +1,8 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
+extern crate alloc;
+
+use alloc::{string::String, vec::Vec};
 use core::{fmt, future::Future, str::FromStr};
 
 use starknet_types_core::felt::Felt;
@@ -114,10 +117,42 @@ pub struct ProofRequest<'a> {
     pub poseidon: &'a [Felt],
 }
 
+/// The bounds of a single memory segment, as echoed back by the prover alongside a [`Proof`].
+///
+/// This is an owned counterpart to [`MemorySegment`], which borrows its name from the
+/// [`ProofRequest`] that produced the proof.
+#[derive(Debug, Clone)]
+pub struct MemorySegmentBounds {
+    pub name: String,
+    pub start: Felt,
+    pub end: Felt,
+}
+
+/// The public input that a prover echoes back alongside the generated proof.
+///
+/// This is the owned, prover-agnostic counterpart of the public-input fields found on
+/// [`ProofRequest`], reconstructed from whatever the prover actually used to produce the proof
+/// (as opposed to what was requested).
+#[derive(Debug, Clone)]
+pub struct ProvenPublicInput {
+    pub layout: Layout,
+    pub rc_min: isize,
+    pub rc_max: isize,
+    pub n_steps: usize,
+    pub memory_segments: Vec<MemorySegmentBounds>,
+    /// The values held in the `output` memory segment, in address order.
+    pub output: Vec<Felt>,
+}
+
 /// The response produced by a prover. This is the return type produced by the [`Prove::prove`]
 /// method.
 #[derive(Debug, Clone)]
-pub struct Proof {}
+pub struct Proof {
+    /// The serialized STARK proof, in whatever binary format the prover that produced it uses.
+    pub proof: Vec<u8>,
+    /// The public input that the prover echoed back alongside `proof`.
+    pub public_input: ProvenPublicInput,
+}
 
 /// This trait encapsulates the behavior of a Starknet proving mechanism.
 ///
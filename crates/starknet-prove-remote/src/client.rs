@@ -0,0 +1,72 @@
+use std::{net::SocketAddr, path::PathBuf};
+
+use starknet_prove_core::{Proof, ProofRequest, Prove};
+use tokio::net::TcpStream;
+#[cfg(unix)]
+use tokio::net::UnixStream;
+
+use crate::{
+    framing::{read_message, write_message},
+    wire::{WireProofRequest, WireResponse},
+    Error,
+};
+
+/// The address of a [`ProverServer`](crate::ProverServer) to dispatch proofs to.
+#[derive(Debug, Clone)]
+pub enum RemoteAddr {
+    /// Connect over TCP.
+    Tcp(SocketAddr),
+    /// Connect over a Unix domain socket.
+    #[cfg(unix)]
+    Unix(PathBuf),
+}
+
+/// A [`Prove`] implementation that dispatches [`ProofRequest`]s to a
+/// [`ProverServer`](crate::ProverServer) running on a separate machine, over the network.
+///
+/// A fresh connection is opened for every call to [`prove`](Prove::prove); [`ProverServer`]
+/// handles one request/response exchange per connection.
+///
+/// [`ProverServer`]: crate::ProverServer
+#[derive(Debug, Clone)]
+pub struct RemoteProver {
+    addr: RemoteAddr,
+}
+
+impl RemoteProver {
+    /// Creates a new [`RemoteProver`] that dispatches proofs to the prover manager listening at
+    /// `addr`.
+    pub fn new(addr: RemoteAddr) -> Self {
+        Self { addr }
+    }
+}
+
+impl Prove for RemoteProver {
+    type Err = Error;
+
+    async fn prove(&mut self, request: &ProofRequest<'_>) -> Result<Proof, Self::Err> {
+        let wire_request = WireProofRequest::from(request);
+
+        let response: WireResponse = match &self.addr {
+            RemoteAddr::Tcp(addr) => {
+                let mut stream = TcpStream::connect(addr).await?;
+                write_message(&mut stream, &wire_request).await?;
+                read_message(&mut stream).await?
+            }
+            #[cfg(unix)]
+            RemoteAddr::Unix(path) => {
+                let mut stream = UnixStream::connect(path).await?;
+                write_message(&mut stream, &wire_request).await?;
+                read_message(&mut stream).await?
+            }
+        };
+
+        match response {
+            WireResponse::Ok(proof) => proof.into_proof(),
+            WireResponse::Err(err) => Err(Error::Remote {
+                kind: err.kind,
+                message: err.message,
+            }),
+        }
+    }
+}
@@ -0,0 +1,55 @@
+//! The wire format shared by [`RemoteProver`](crate::RemoteProver) and
+//! [`ProverServer`](crate::ProverServer): every message is a single frame made of a one-byte
+//! protocol version, a four-byte big-endian length, and a JSON-encoded payload.
+
+use serde::{de::DeserializeOwned, Serialize};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+use crate::{Error, PROTOCOL_VERSION};
+
+/// The largest frame we're willing to allocate for, in bytes.
+///
+/// Traces and memory blobs for even very large programs stay well under this; anything bigger
+/// is almost certainly a malformed or malicious length prefix rather than a legitimate payload.
+pub(crate) const MAX_FRAME_LEN: u32 = 512 * 1024 * 1024;
+
+/// Writes `message` as a single versioned, length-prefixed frame.
+pub(crate) async fn write_message<W, T>(writer: &mut W, message: &T) -> Result<(), Error>
+where
+    W: AsyncWrite + Unpin,
+    T: Serialize,
+{
+    let payload = serde_json::to_vec(message)?;
+
+    writer.write_u8(PROTOCOL_VERSION).await?;
+    writer.write_u32(payload.len() as u32).await?;
+    writer.write_all(&payload).await?;
+    writer.flush().await?;
+
+    Ok(())
+}
+
+/// Reads a single versioned, length-prefixed frame.
+pub(crate) async fn read_message<R, T>(reader: &mut R) -> Result<T, Error>
+where
+    R: AsyncRead + Unpin,
+    T: DeserializeOwned,
+{
+    let version = reader.read_u8().await?;
+    if version != PROTOCOL_VERSION {
+        return Err(Error::UnsupportedVersion(version));
+    }
+
+    let len = reader.read_u32().await?;
+    if len > MAX_FRAME_LEN {
+        return Err(Error::FrameTooLarge {
+            len,
+            max: MAX_FRAME_LEN,
+        });
+    }
+
+    let mut payload = vec![0u8; len as usize];
+    reader.read_exact(&mut payload).await?;
+
+    Ok(serde_json::from_slice(&payload)?)
+}
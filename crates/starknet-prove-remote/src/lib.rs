@@ -0,0 +1,84 @@
+//! A network-backed [`Prove`] implementation.
+//!
+//! [`RemoteProver`] serializes a [`ProofRequest`] and dispatches it to a [`ProverServer`] running
+//! on a separate machine (typically part of a pool dedicated to running the actual,
+//! CPU/RAM-heavy prover), so that callers can stay generic over [`Prove`] while offloading the
+//! proving work itself.
+//!
+//! The wire protocol is a single length-prefixed, versioned request/response exchange per
+//! connection; see the `framing` module for the exact format.
+//!
+//! [`ProofRequest`]: starknet_prove_core::ProofRequest
+//! [`Prove`]: starknet_prove_core::Prove
+
+use std::io;
+
+use serde::{Deserialize, Serialize};
+
+pub use self::client::{RemoteAddr, RemoteProver};
+pub use self::server::ProverServer;
+
+mod client;
+mod framing;
+mod server;
+mod wire;
+
+/// The current version of the wire protocol spoken by [`RemoteProver`] and [`ProverServer`].
+const PROTOCOL_VERSION: u8 = 1;
+
+/// An error that can be produced by [`RemoteProver`] or while serving a [`ProverServer`]
+/// connection.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// A system-level error occured while talking to the peer.
+    #[error("{0}")]
+    Io(
+        #[source]
+        #[from]
+        io::Error,
+    ),
+    /// The peer sent a message for a protocol version we don't support.
+    #[error("unsupported protocol version {0}")]
+    UnsupportedVersion(u8),
+    /// The peer announced a frame larger than [`framing::MAX_FRAME_LEN`].
+    #[error("frame length {len} exceeds the maximum of {max} bytes")]
+    FrameTooLarge {
+        /// The length the peer announced, in bytes.
+        len: u32,
+        /// The maximum frame length we're willing to allocate for.
+        max: u32,
+    },
+    /// A message could not be encoded or decoded.
+    #[error("{0}")]
+    Serde(
+        #[source]
+        #[from]
+        serde_json::Error,
+    ),
+    /// The remote prover manager rejected the request, or failed to generate the proof.
+    #[error("the remote prover failed: {message}")]
+    Remote {
+        /// A coarse classification of why the request failed, letting callers branch on the
+        /// failure mode instead of string-sniffing `message`.
+        kind: RemoteErrorKind,
+        /// A human-readable description of what went wrong, as reported by the remote
+        /// [`ProverServer`](crate::ProverServer).
+        message: String,
+    },
+}
+
+/// A coarse classification of why a [`RemoteProver`] request failed.
+///
+/// [`ProverServer`] is generic over the underlying [`Prove`](starknet_prove_core::Prove)
+/// implementation it serves, so it can't mirror that implementation's own error variants over
+/// the wire; this distinguishes the cases it can always tell apart on its own.
+///
+/// [`ProverServer`]: crate::ProverServer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RemoteErrorKind {
+    /// The request was rejected before it ever reached the underlying prover (e.g. an unknown
+    /// layout).
+    InvalidRequest,
+    /// The underlying prover failed to generate the proof.
+    Prover,
+}
@@ -0,0 +1,131 @@
+use std::{fmt, sync::Arc};
+
+use starknet_prove_core::{MemoryEntry, MemorySegment, ProofRequest, Prove};
+use starknet_types_core::felt::Felt;
+use tokio::{
+    io::{AsyncRead, AsyncWrite},
+    sync::Mutex,
+};
+
+use crate::{
+    framing::{read_message, write_message},
+    wire::{felt_from_bytes, WireError, WireProofRequest, WireResponse},
+    Error, RemoteErrorKind,
+};
+
+/// Accepts connections and runs proving jobs on behalf of [`RemoteProver`](crate::RemoteProver)
+/// clients, via an underlying [`Prove`] implementation (typically a `StoneProver`).
+///
+/// Callers are expected to accept connections themselves (e.g. on a
+/// [`TcpListener`](tokio::net::TcpListener) or
+/// `UnixListener`) and hand each one to [`handle_connection`](Self::handle_connection), which
+/// serves a single request/response exchange before returning.
+pub struct ProverServer<P> {
+    prover: Arc<Mutex<P>>,
+}
+
+impl<P> Clone for ProverServer<P> {
+    fn clone(&self) -> Self {
+        Self {
+            prover: self.prover.clone(),
+        }
+    }
+}
+
+impl<P> ProverServer<P>
+where
+    P: Prove,
+    P::Err: fmt::Display,
+{
+    /// Creates a new [`ProverServer`] running jobs through `prover`.
+    pub fn new(prover: P) -> Self {
+        Self {
+            prover: Arc::new(Mutex::new(prover)),
+        }
+    }
+
+    /// Reads a single [`ProofRequest`] off `stream`, runs it, and writes the resulting response
+    /// back.
+    pub async fn handle_connection<S>(&self, mut stream: S) -> Result<(), Error>
+    where
+        S: AsyncRead + AsyncWrite + Unpin,
+    {
+        let wire_request: WireProofRequest = read_message(&mut stream).await?;
+        let response = self.run_request(wire_request).await;
+        write_message(&mut stream, &response).await
+    }
+
+    /// Decodes `wire_request`, runs it through the underlying prover, and returns the
+    /// response that should be sent back, mapping any prover error onto [`WireResponse::Err`].
+    async fn run_request(&self, wire_request: WireProofRequest) -> WireResponse {
+        let layout = match wire_request.layout.parse() {
+            Ok(layout) => layout,
+            Err(()) => {
+                return WireResponse::Err(WireError {
+                    kind: RemoteErrorKind::InvalidRequest,
+                    message: format!("unknown layout {:?}", wire_request.layout),
+                })
+            }
+        };
+
+        let segment_names: Vec<String> = wire_request
+            .memory_segments
+            .iter()
+            .map(|segment| segment.name.clone())
+            .collect();
+        let memory_segments: Vec<MemorySegment> = wire_request
+            .memory_segments
+            .iter()
+            .zip(&segment_names)
+            .map(|(segment, name)| MemorySegment {
+                name,
+                start: felt_from_bytes(segment.start),
+                end: felt_from_bytes(segment.end),
+            })
+            .collect();
+        let public_memory: Vec<MemoryEntry> = wire_request
+            .public_memory
+            .iter()
+            .map(MemoryEntry::from)
+            .collect();
+
+        let felts = |bytes: &[[u8; 32]]| -> Vec<Felt> {
+            bytes.iter().copied().map(felt_from_bytes).collect()
+        };
+        let pedersen = felts(&wire_request.pedersen);
+        let range_check = felts(&wire_request.range_check);
+        let ecdsa = felts(&wire_request.ecdsa);
+        let bitwise = felts(&wire_request.bitwise);
+        let ec_ops = felts(&wire_request.ec_ops);
+        let keccak = felts(&wire_request.keccak);
+        let poseidon = felts(&wire_request.poseidon);
+
+        let request = ProofRequest {
+            layout,
+            rc_min: wire_request.rc_min,
+            rc_max: wire_request.rc_max,
+            n_steps: wire_request.n_steps,
+            memory_segments: &memory_segments,
+            public_memory: &public_memory,
+            dynamic_params: (),
+            trace: &wire_request.trace,
+            memory: &wire_request.memory,
+            pedersen: &pedersen,
+            range_check: &range_check,
+            ecdsa: &ecdsa,
+            bitwise: &bitwise,
+            ec_ops: &ec_ops,
+            keccak: &keccak,
+            poseidon: &poseidon,
+        };
+
+        let mut prover = self.prover.lock().await;
+        match prover.prove(&request).await {
+            Ok(proof) => WireResponse::Ok(proof.into()),
+            Err(err) => WireResponse::Err(WireError {
+                kind: RemoteErrorKind::Prover,
+                message: err.to_string(),
+            }),
+        }
+    }
+}
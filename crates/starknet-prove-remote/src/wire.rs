@@ -0,0 +1,222 @@
+//! Owned, serializable counterparts of the borrowing types found in `starknet-prove-core`.
+
+use serde::{Deserialize, Serialize};
+use starknet_prove_core::{MemoryEntry, MemorySegment, MemorySegmentBounds, Proof, ProvenPublicInput};
+use starknet_types_core::felt::Felt;
+
+use crate::{Error, RemoteErrorKind};
+
+pub(crate) fn felt_to_bytes(felt: &Felt) -> [u8; 32] {
+    felt.to_bytes_be()
+}
+
+pub(crate) fn felt_from_bytes(bytes: [u8; 32]) -> Felt {
+    Felt::from_bytes_be(&bytes)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct WireMemorySegment {
+    pub name: String,
+    pub start: [u8; 32],
+    pub end: [u8; 32],
+}
+
+impl From<&MemorySegment<'_>> for WireMemorySegment {
+    fn from(segment: &MemorySegment<'_>) -> Self {
+        Self {
+            name: segment.name.to_owned(),
+            start: felt_to_bytes(&segment.start),
+            end: felt_to_bytes(&segment.end),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct WireMemoryEntry {
+    pub address: usize,
+    pub value: [u8; 32],
+    pub page: usize,
+}
+
+impl From<&MemoryEntry> for WireMemoryEntry {
+    fn from(entry: &MemoryEntry) -> Self {
+        Self {
+            address: entry.address,
+            value: felt_to_bytes(&entry.value),
+            page: entry.page,
+        }
+    }
+}
+
+impl From<&WireMemoryEntry> for MemoryEntry {
+    fn from(entry: &WireMemoryEntry) -> Self {
+        Self {
+            address: entry.address,
+            value: felt_from_bytes(entry.value),
+            page: entry.page,
+        }
+    }
+}
+
+/// The serialized form of a [`ProofRequest`](starknet_prove_core::ProofRequest).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct WireProofRequest {
+    pub layout: String,
+    pub rc_min: isize,
+    pub rc_max: isize,
+    pub n_steps: usize,
+    pub memory_segments: Vec<WireMemorySegment>,
+    pub public_memory: Vec<WireMemoryEntry>,
+    pub trace: Vec<u8>,
+    pub memory: Vec<u8>,
+    pub pedersen: Vec<[u8; 32]>,
+    pub range_check: Vec<[u8; 32]>,
+    pub ecdsa: Vec<[u8; 32]>,
+    pub bitwise: Vec<[u8; 32]>,
+    pub ec_ops: Vec<[u8; 32]>,
+    pub keccak: Vec<[u8; 32]>,
+    pub poseidon: Vec<[u8; 32]>,
+}
+
+impl From<&starknet_prove_core::ProofRequest<'_>> for WireProofRequest {
+    fn from(request: &starknet_prove_core::ProofRequest<'_>) -> Self {
+        let felts = |slice: &[Felt]| slice.iter().map(felt_to_bytes).collect();
+
+        Self {
+            layout: request.layout.name().to_owned(),
+            rc_min: request.rc_min,
+            rc_max: request.rc_max,
+            n_steps: request.n_steps,
+            memory_segments: request
+                .memory_segments
+                .iter()
+                .map(WireMemorySegment::from)
+                .collect(),
+            public_memory: request
+                .public_memory
+                .iter()
+                .map(WireMemoryEntry::from)
+                .collect(),
+            trace: request.trace.to_vec(),
+            memory: request.memory.to_vec(),
+            pedersen: felts(request.pedersen),
+            range_check: felts(request.range_check),
+            ecdsa: felts(request.ecdsa),
+            bitwise: felts(request.bitwise),
+            ec_ops: felts(request.ec_ops),
+            keccak: felts(request.keccak),
+            poseidon: felts(request.poseidon),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct WireMemorySegmentBounds {
+    pub name: String,
+    pub start: [u8; 32],
+    pub end: [u8; 32],
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct WireProvenPublicInput {
+    pub layout: String,
+    pub rc_min: isize,
+    pub rc_max: isize,
+    pub n_steps: usize,
+    pub memory_segments: Vec<WireMemorySegmentBounds>,
+    pub output: Vec<[u8; 32]>,
+}
+
+/// The serialized form of a [`Proof`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct WireProof {
+    pub proof: Vec<u8>,
+    pub public_input: WireProvenPublicInput,
+}
+
+impl From<Proof> for WireProof {
+    fn from(proof: Proof) -> Self {
+        Self {
+            proof: proof.proof,
+            public_input: WireProvenPublicInput {
+                layout: proof.public_input.layout.name().to_owned(),
+                rc_min: proof.public_input.rc_min,
+                rc_max: proof.public_input.rc_max,
+                n_steps: proof.public_input.n_steps,
+                memory_segments: proof
+                    .public_input
+                    .memory_segments
+                    .into_iter()
+                    .map(|segment| WireMemorySegmentBounds {
+                        name: segment.name,
+                        start: felt_to_bytes(&segment.start),
+                        end: felt_to_bytes(&segment.end),
+                    })
+                    .collect(),
+                output: proof
+                    .public_input
+                    .output
+                    .iter()
+                    .map(felt_to_bytes)
+                    .collect(),
+            },
+        }
+    }
+}
+
+impl WireProof {
+    /// Converts this wire value back into a [`Proof`], failing if it carries an unknown layout.
+    pub(crate) fn into_proof(self) -> Result<Proof, Error> {
+        let layout = self
+            .public_input
+            .layout
+            .parse()
+            .map_err(|_| {
+                Error::Serde(<serde_json::Error as serde::de::Error>::custom(format!(
+                    "unknown layout {:?}",
+                    self.public_input.layout
+                )))
+            })?;
+
+        Ok(Proof {
+            proof: self.proof,
+            public_input: ProvenPublicInput {
+                layout,
+                rc_min: self.public_input.rc_min,
+                rc_max: self.public_input.rc_max,
+                n_steps: self.public_input.n_steps,
+                memory_segments: self
+                    .public_input
+                    .memory_segments
+                    .into_iter()
+                    .map(|segment| MemorySegmentBounds {
+                        name: segment.name,
+                        start: felt_from_bytes(segment.start),
+                        end: felt_from_bytes(segment.end),
+                    })
+                    .collect(),
+                output: self
+                    .public_input
+                    .output
+                    .into_iter()
+                    .map(felt_from_bytes)
+                    .collect(),
+            },
+        })
+    }
+}
+
+/// The serialized form of a failed request, letting [`RemoteProver`](crate::RemoteProver)
+/// distinguish failure modes without string-sniffing [`Error::Remote`]'s message.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct WireError {
+    pub kind: RemoteErrorKind,
+    pub message: String,
+}
+
+/// The response half of the wire protocol.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) enum WireResponse {
+    Ok(WireProof),
+    Err(WireError),
+}
@@ -0,0 +1,189 @@
+use std::{
+    ffi::OsString,
+    path::{Path, PathBuf},
+    process::Stdio,
+    time::Duration,
+};
+
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    process::Command,
+    time::Instant,
+};
+use tokio_vsock::{VsockAddr, VsockStream};
+
+use super::ProverBackend;
+use crate::{
+    Error, MEMORY_FILE, PARAMETER_FILE, PRIVATE_INPUT_FILE, PROVER_CONFIG_FILE, PUBLIC_INPUT_FILE,
+    TRACE_FILE,
+};
+
+/// The port the in-VM agent listens on for proving jobs.
+const AGENT_PORT: u32 = 9000;
+
+/// How long to wait between connection attempts while the guest boots.
+const BOOT_RETRY_INTERVAL: Duration = Duration::from_millis(100);
+
+/// The total amount of time we're willing to wait for the guest to accept a vsock connection
+/// before giving up and killing the hypervisor.
+///
+/// Booting the kernel, mounting the initramfs and starting the in-VM agent routinely takes a
+/// couple of seconds; this leaves comfortable headroom on top of that.
+const BOOT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The largest frame we're willing to allocate for when reading the agent's reply, in bytes.
+///
+/// A `proof_file.json` for even very large programs stays well under this; anything bigger is
+/// almost certainly a compromised or misbehaving in-VM agent rather than a legitimate proof.
+const MAX_FRAME_LEN: u32 = 512 * 1024 * 1024;
+
+/// Configuration for [`MicroVmBackend`].
+#[derive(Debug, Clone)]
+pub struct MicroVmConfig {
+    /// Path to the guest kernel image booted for every job.
+    pub kernel: PathBuf,
+    /// Path to the initramfs containing the in-VM agent and `cpu_air_prover`.
+    pub initramfs: PathBuf,
+    /// The vsock context ID (CID) the guest will listen on.
+    pub vsock_cid: u32,
+    /// The hypervisor command used to boot the micro VM (e.g. `firecracker`, `cloud-hypervisor`).
+    pub hypervisor_cmd: OsString,
+}
+
+/// Runs the Stone prover inside a minimal, sandboxed virtual machine.
+///
+/// Unlike [`ProcessBackend`](super::ProcessBackend), the prover never gets direct access to the
+/// host file system: input files are streamed to an in-VM agent and the resulting proof is
+/// streamed back over a `virtio-vsock` channel, so a malicious `cpu_air_prover` binary can, at
+/// worst, corrupt its own disposable VM rather than the host.
+///
+/// The wire protocol between the host and the in-VM agent is a sequence of length-prefixed
+/// frames: the host sends the contents of [`PUBLIC_INPUT_FILE`], [`PRIVATE_INPUT_FILE`],
+/// [`PROVER_CONFIG_FILE`], [`PARAMETER_FILE`], [`TRACE_FILE`] and [`MEMORY_FILE`] (the last two
+/// may be empty frames, since some callers inline them into the private input), in that order,
+/// and the agent replies with a single frame containing `proof_file.json`.
+#[derive(Debug, Clone)]
+pub struct MicroVmBackend {
+    config: MicroVmConfig,
+}
+
+impl MicroVmBackend {
+    /// Creates a new [`MicroVmBackend`] from the given configuration.
+    pub fn new(config: MicroVmConfig) -> Self {
+        Self { config }
+    }
+}
+
+impl ProverBackend for MicroVmBackend {
+    async fn run(&self, job_directory: &Path) -> Result<Vec<u8>, Error> {
+        let mut hypervisor = make_hypervisor_command(&self.config).spawn()?;
+        let mut guest = match connect_with_retry(self.config.vsock_cid).await {
+            Ok(guest) => guest,
+            Err(err) => {
+                let _ = hypervisor.start_kill();
+                let _ = hypervisor.wait().await;
+                return Err(err);
+            }
+        };
+
+        send_file(&mut guest, &job_directory.join(PUBLIC_INPUT_FILE)).await?;
+        send_file(&mut guest, &job_directory.join(PRIVATE_INPUT_FILE)).await?;
+        send_file(&mut guest, &job_directory.join(PROVER_CONFIG_FILE)).await?;
+        send_file(&mut guest, &job_directory.join(PARAMETER_FILE)).await?;
+        send_optional_file(&mut guest, &job_directory.join(TRACE_FILE)).await?;
+        send_optional_file(&mut guest, &job_directory.join(MEMORY_FILE)).await?;
+
+        let proof = read_frame(&mut guest).await?;
+
+        // The agent shuts its side down once the proof has been sent; wait for the VM to exit so
+        // that the hypervisor process doesn't outlive the job (`kill_on_drop` is the backstop if
+        // it never does).
+        let status = hypervisor.wait().await?;
+        if !status.success() {
+            return Err(Error::UnexpectedErrorCode(
+                status,
+                "the micro VM hypervisor exited with an error".into(),
+            ));
+        }
+
+        Ok(proof)
+    }
+}
+
+/// Repeatedly attempts to connect to the in-VM agent until it accepts the connection or
+/// [`BOOT_TIMEOUT`] elapses.
+///
+/// The guest needs time to boot the kernel, mount the initramfs and start the agent before its
+/// vsock listener comes up, so the very first attempt is expected to fail in practice.
+async fn connect_with_retry(vsock_cid: u32) -> Result<VsockStream, Error> {
+    let addr = VsockAddr::new(vsock_cid, AGENT_PORT);
+    let deadline = Instant::now() + BOOT_TIMEOUT;
+
+    loop {
+        match VsockStream::connect(addr).await {
+            Ok(guest) => return Ok(guest),
+            Err(_) if Instant::now() < deadline => {
+                tokio::time::sleep(BOOT_RETRY_INTERVAL).await;
+            }
+            Err(_) => return Err(Error::MicroVmBootTimeout(BOOT_TIMEOUT)),
+        }
+    }
+}
+
+/// Returns the [`Command`] used to boot the micro VM described by `config`.
+fn make_hypervisor_command(config: &MicroVmConfig) -> Command {
+    let mut command = Command::new(&config.hypervisor_cmd);
+
+    command
+        .arg("--kernel")
+        .arg(&config.kernel)
+        .arg("--initrd")
+        .arg(&config.initramfs)
+        .arg("--vsock-cid")
+        .arg(config.vsock_cid.to_string())
+        .env_clear() // cleared for security
+        .stdout(Stdio::null())
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .kill_on_drop(true); // ensures the VM is torn down if the job is cancelled
+
+    command
+}
+
+/// Sends the contents of `path` as a single length-prefixed frame.
+async fn send_file(stream: &mut VsockStream, path: &Path) -> Result<(), Error> {
+    let contents = tokio::fs::read(path).await?;
+    write_frame(stream, &contents).await
+}
+
+/// Like [`send_file`], but sends an empty frame instead of failing if `path` does not exist.
+async fn send_optional_file(stream: &mut VsockStream, path: &Path) -> Result<(), Error> {
+    let contents = match tokio::fs::read(path).await {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+        Err(err) => return Err(err.into()),
+    };
+    write_frame(stream, &contents).await
+}
+
+/// Writes `payload` as a single length-prefixed frame.
+async fn write_frame(stream: &mut VsockStream, payload: &[u8]) -> Result<(), Error> {
+    stream.write_u32(payload.len() as u32).await?;
+    stream.write_all(payload).await?;
+    Ok(())
+}
+
+/// Reads a single length-prefixed frame.
+async fn read_frame(stream: &mut VsockStream) -> Result<Vec<u8>, Error> {
+    let len = stream.read_u32().await?;
+    if len > MAX_FRAME_LEN {
+        return Err(Error::FrameTooLarge {
+            len,
+            max: MAX_FRAME_LEN,
+        });
+    }
+
+    let mut buf = vec![0u8; len as usize];
+    stream.read_exact(&mut buf).await?;
+    Ok(buf)
+}
@@ -0,0 +1,21 @@
+//! Abstracts over how the `cpu_air_prover` binary configured on [`StoneProver`] is actually
+//! executed, so that it can run directly on the host or inside an isolated environment.
+//!
+//! [`StoneProver`]: crate::StoneProver
+
+use std::{future::Future, path::Path};
+
+use crate::Error;
+
+mod microvm;
+mod process;
+
+pub use self::microvm::{MicroVmBackend, MicroVmConfig};
+pub use self::process::ProcessBackend;
+
+/// Runs the Stone prover against the inputs written to a job's working directory.
+pub trait ProverBackend {
+    /// Runs the prover against the input files found in `job_directory`, returning the raw bytes
+    /// of the resulting `proof_file.json` on success.
+    fn run(&self, job_directory: &Path) -> impl Future<Output = Result<Vec<u8>, Error>> + Send;
+}
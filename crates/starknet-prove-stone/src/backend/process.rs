@@ -0,0 +1,99 @@
+use std::{ffi::OsString, path::Path, process::Stdio};
+
+use tokio::{
+    io::AsyncReadExt,
+    process::{ChildStderr, Command},
+};
+
+use super::ProverBackend;
+use crate::{
+    Error, PARAMETER_FILE, PRIVATE_INPUT_FILE, PROOF_FILE, PROVER_CONFIG_FILE, PUBLIC_INPUT_FILE,
+};
+
+/// Runs the Stone prover as a plain child process on the host.
+///
+/// This is the default backend, and the one used prior to the introduction of
+/// [`ProverBackend`].
+///
+/// # Security concerns
+///
+/// Because an external process is being spawned and given access to the file system, it is
+/// important to make sure that the *correct* command is being run.
+///
+/// Currently, no `chroot` or other sandboxing mechanism is being used to run the prover, meaning
+/// that if a malicious command is used inadvertedly, it could potentially access or modify the
+/// entire file system. [`MicroVmBackend`](super::MicroVmBackend) is a sandboxed alternative.
+///
+/// The environment is cleared to make sure that the prover can't access unnescessary information.
+#[derive(Debug, Clone)]
+pub struct ProcessBackend {
+    /// The command that will be spawned every time a proof is requested.
+    ///
+    /// Note that this is relative to the job's working directory (unless the path is absolute or
+    /// is a command).
+    pub command: OsString,
+}
+
+impl Default for ProcessBackend {
+    fn default() -> Self {
+        Self {
+            command: "cpu_air_prover".into(),
+        }
+    }
+}
+
+impl ProverBackend for ProcessBackend {
+    async fn run(&self, job_directory: &Path) -> Result<Vec<u8>, Error> {
+        let mut command = make_command(&self.command, job_directory);
+        let mut child = command.spawn()?;
+        let stderr = child.stderr.take().unwrap();
+        let status = child.wait().await?;
+
+        // If the command has failed, we read the error message from the standard error stream
+        // and return it.
+        if !status.success() {
+            let error_message = error_message(stderr).await;
+            return Err(Error::UnexpectedErrorCode(status, error_message));
+        }
+
+        Ok(tokio::fs::read(job_directory.join(PROOF_FILE)).await?)
+    }
+}
+
+/// Returns the [`Command`] used to spawn `program` against the inputs found in `job_directory`.
+fn make_command(program: &OsString, job_directory: &Path) -> Command {
+    let mut command = Command::new(program);
+
+    command
+        .current_dir(job_directory)
+        .env_clear() // cleared for security
+        .arg("--out_file")
+        .arg(PROOF_FILE)
+        .arg("--private_input_file")
+        .arg(PRIVATE_INPUT_FILE)
+        .arg("--public_input_file")
+        .arg(PUBLIC_INPUT_FILE)
+        .arg("--prover-config-file")
+        .arg(PROVER_CONFIG_FILE)
+        .arg("--parameter_file")
+        .arg(PARAMETER_FILE)
+        .stdout(Stdio::null())
+        .stdin(Stdio::null())
+        .stderr(Stdio::piped()) // stderr needs to be piped to capture error messages
+        .kill_on_drop(true); // ensures that any error occuring before the child is waited on kills it
+
+    command
+}
+
+/// Gets the error message stored in the standard error stream of a child process.
+async fn error_message(mut stderr: ChildStderr) -> String {
+    let mut buf = Vec::new();
+    match stderr.read_to_end(&mut buf).await {
+        Ok(_) => (),
+        Err(_) => return "<failed to read error message>".into(),
+    }
+    match String::from_utf8(buf) {
+        Ok(s) => s,
+        Err(_) => "<error message is not valid UTF-8>".into(),
+    }
+}
@@ -0,0 +1,170 @@
+//! Cancellable, pollable proving jobs spawned by [`StoneProver::prove_job`].
+//!
+//! [`StoneProver::prove_job`]: crate::StoneProver::prove_job
+
+use std::{
+    collections::HashMap,
+    fmt,
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use starknet_prove_core::Proof;
+use tokio::{sync::oneshot, task::AbortHandle};
+
+use crate::Error;
+
+/// Uniquely identifies a proving job created by [`StoneProver::prove_job`].
+///
+/// [`StoneProver::prove_job`]: crate::StoneProver::prove_job
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct JobId(u64);
+
+impl fmt::Display for JobId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:x}", self.0)
+    }
+}
+
+/// The state of a proving job created by [`StoneProver::prove_job`].
+///
+/// [`StoneProver::prove_job`]: crate::StoneProver::prove_job
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JobStatus {
+    /// The job's inputs have been written but its child process has not been spawned yet.
+    Queued,
+    /// The job's child process has been spawned and is running.
+    Running,
+    /// The job finished successfully.
+    Succeeded,
+    /// The job finished with an error.
+    Failed,
+    /// The job was cancelled before it could complete.
+    Cancelled,
+}
+
+/// Atomically moves `status` from `from` to `to`, returning whether the transition happened.
+///
+/// This is the single source of truth for terminal transitions: [`ProveHandle::cancel`] and the
+/// task spawned by [`StoneProver::prove_job`] race to move the status out of `Queued`/`Running`,
+/// and whichever one wins the compare-and-swap is the sole owner of the cleanup that follows
+/// (removing the job from the registry, deleting its working directory, and — for the task —
+/// sending the result). The loser does nothing, so a result is never stomped on and the working
+/// directory is never deleted twice or leaked.
+///
+/// [`StoneProver::prove_job`]: crate::StoneProver::prove_job
+pub(crate) fn try_transition(status: &Mutex<JobStatus>, from: JobStatus, to: JobStatus) -> bool {
+    let mut guard = status.lock().unwrap();
+    if *guard == from {
+        *guard = to;
+        true
+    } else {
+        false
+    }
+}
+
+/// Tracks the per-job working directories of the jobs currently spawned by a [`StoneProver`].
+///
+/// [`StoneProver`]: crate::StoneProver
+#[derive(Debug, Default, Clone)]
+pub(crate) struct JobRegistry {
+    next_id: Arc<AtomicU64>,
+    dirs: Arc<Mutex<HashMap<JobId, PathBuf>>>,
+}
+
+impl JobRegistry {
+    /// Allocates a new, not-yet-tracked [`JobId`].
+    pub(crate) fn alloc(&self) -> JobId {
+        JobId(self.next_id.fetch_add(1, Ordering::Relaxed))
+    }
+
+    /// Registers the working directory used by `id`.
+    pub(crate) fn track(&self, id: JobId, dir: PathBuf) {
+        self.dirs.lock().unwrap().insert(id, dir);
+    }
+
+    /// Removes a job from the registry, returning its working directory if it was still present.
+    pub(crate) fn remove(&self, id: JobId) -> Option<PathBuf> {
+        self.dirs.lock().unwrap().remove(&id)
+    }
+}
+
+/// A handle to a proving job spawned by [`StoneProver::prove_job`].
+///
+/// Dropping or [cancelling](ProveHandle::cancel) the handle kills the job's child process (the
+/// existing `kill_on_drop(true)` on the spawned [`Command`](tokio::process::Command) is the
+/// backstop) and removes its per-job working directory.
+pub struct ProveHandle {
+    id: JobId,
+    status: Arc<Mutex<JobStatus>>,
+    abort: AbortHandle,
+    result: oneshot::Receiver<Result<Proof, Error>>,
+    registry: JobRegistry,
+}
+
+impl ProveHandle {
+    pub(crate) fn new(
+        id: JobId,
+        status: Arc<Mutex<JobStatus>>,
+        abort: AbortHandle,
+        result: oneshot::Receiver<Result<Proof, Error>>,
+        registry: JobRegistry,
+    ) -> Self {
+        Self {
+            id,
+            status,
+            abort,
+            result,
+            registry,
+        }
+    }
+
+    /// Returns the opaque identifier of this job.
+    pub fn id(&self) -> JobId {
+        self.id
+    }
+
+    /// Returns a snapshot of the job's current status.
+    pub fn status(&self) -> JobStatus {
+        *self.status.lock().unwrap()
+    }
+
+    /// Cancels the job.
+    ///
+    /// This kills the job's child process and removes its working directory. Calling this after
+    /// the job has already reached a terminal status (successfully, with an error, or because it
+    /// was already cancelled) is a no-op: see [`try_transition`] for why this can't race the
+    /// background task into leaking the working directory or contradicting a result it already
+    /// produced.
+    pub fn cancel(&mut self) {
+        let cancelled = try_transition(&self.status, JobStatus::Queued, JobStatus::Cancelled)
+            || try_transition(&self.status, JobStatus::Running, JobStatus::Cancelled);
+        if !cancelled {
+            return;
+        }
+
+        self.abort.abort();
+
+        if let Some(dir) = self.registry.remove(self.id) {
+            tokio::spawn(async move {
+                let _ = tokio::fs::remove_dir_all(dir).await;
+            });
+        }
+    }
+
+    /// Waits for the job to finish and returns its result.
+    ///
+    /// Returns [`Error::Cancelled`] if the job was cancelled before it completed.
+    pub async fn wait(mut self) -> Result<Proof, Error> {
+        (&mut self.result).await.unwrap_or(Err(Error::Cancelled))
+    }
+}
+
+impl Drop for ProveHandle {
+    fn drop(&mut self) {
+        self.cancel();
+    }
+}
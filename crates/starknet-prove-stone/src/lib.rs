@@ -1,18 +1,24 @@
-use starknet_prove_core::{Proof, ProofRequest, Prove};
+use starknet_prove_core::{MemorySegmentBounds, Proof, ProofRequest, ProvenPublicInput, Prove};
 use std::{
-    ffi::OsString,
+    collections::BTreeMap,
     io,
-    path::PathBuf,
-    process::{ExitStatus, Stdio},
-};
-use tokio::{
-    io::AsyncReadExt,
-    process::{ChildStderr, Command},
+    path::{Path, PathBuf},
+    process::ExitStatus,
+    sync::{Arc, Mutex},
 };
+use tokio::sync::oneshot;
 
 use self::input::write_inputs_to_directory;
+use self::job::JobRegistry;
+
+pub use self::backend::{MicroVmBackend, MicroVmConfig, ProcessBackend, ProverBackend};
+pub use self::job::{JobId, JobStatus, ProveHandle};
 
+mod backend;
 mod input;
+mod job;
+#[cfg(feature = "std")]
+mod permissions;
 
 const PROOF_FILE: &str = "proof_file.json";
 const PRIVATE_INPUT_FILE: &str = "private_input_file.json";
@@ -44,11 +50,28 @@ pub enum Error {
         #[source]
         serde_json::Error,
     ),
+    /// The job was cancelled before it could complete.
+    #[error("the job was cancelled")]
+    Cancelled,
+    /// [`MicroVmBackend`](crate::MicroVmBackend) booted the guest but it never accepted a vsock
+    /// connection within the allotted timeout.
+    #[error("the micro VM did not accept a vsock connection within {0:?}")]
+    MicroVmBootTimeout(std::time::Duration),
+    /// [`MicroVmBackend`](crate::MicroVmBackend) received a frame length announced by the in-VM
+    /// agent larger than it's willing to allocate for.
+    #[error("frame length {len} exceeds the maximum of {max} bytes")]
+    FrameTooLarge { len: u32, max: u32 },
+    /// [`StoneConfig::require_private_working_dir`] is enabled and a component of the working
+    /// directory (or one of its ancestors) is owned by neither the current user nor root, or is
+    /// group- or world-writable.
+    #[cfg(feature = "std")]
+    #[error("{path:?} has insecure permissions (mode {mode:o})")]
+    InsecurePermissions { path: PathBuf, mode: u32 },
 }
 
 /// The configuration passed to [`StoneProver`] to configure its behavior.
 #[derive(Debug, Clone)]
-pub struct StoneConfig {
+pub struct StoneConfig<B = ProcessBackend> {
     /// The working directory in which the Stone prover will be executed.
     ///
     /// This is requiered because [`StoneProver`] invokes a command in the background, and that
@@ -61,41 +84,58 @@ pub struct StoneConfig {
     /// This directory won't be automatically created, so it must exist prior to using the
     /// prover.
     pub working_directory: PathBuf,
-    /// The command that will be spawned every time a proof is requested.
+    /// The on-disk format in which the spawned command writes [`PROOF_FILE`].
+    pub proof_format: ProofFormat,
+    /// The backend responsible for actually running the prover.
     ///
-    /// Note that this is relative to `working_directory` (unless the path is absolute or is a
-    /// command).
-    pub command: OsString,
+    /// Defaults to [`ProcessBackend`], which spawns the prover as a plain, unsandboxed child
+    /// process. Use [`MicroVmBackend`] to run it inside an isolated virtual machine instead.
+    pub backend: B,
+    /// When `true`, [`StoneProver`] verifies, before running the prover for the first time, that
+    /// `working_directory` and all of its ancestors are owned by the current user (or by root)
+    /// and are not group- or world-writable.
+    ///
+    /// This is opt-in (and disabled by default) because it inspects every ancestor of
+    /// `working_directory`, which isn't appropriate for every deployment; enable it whenever
+    /// `working_directory` might be reachable by other local users, to prevent them from
+    /// swapping the prover's inputs or poisoning its output.
+    #[cfg(feature = "std")]
+    pub require_private_working_dir: bool,
 }
 
-impl Default for StoneConfig {
+impl Default for StoneConfig<ProcessBackend> {
     fn default() -> Self {
         Self {
             working_directory: ".".into(),
-            command: "cpu_air_prover".into(),
+            proof_format: ProofFormat::default(),
+            backend: ProcessBackend::default(),
+            #[cfg(feature = "std")]
+            require_private_working_dir: false,
         }
     }
 }
 
+/// The on-disk format of [`PROOF_FILE`] as written by the command configured in [`StoneConfig`].
+///
+/// The Stone prover has historically changed the shape of its output file across versions; this
+/// lets [`StoneConfig`] select which shape to expect without changing the return type of
+/// [`Prove::prove`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ProofFormat {
+    /// The format produced by the first stable release of the Stone prover.
+    #[default]
+    StoneV1,
+}
+
 /// Contains the state required to run the Stone prover in the background and generate proofs with
 /// it.
 ///
 /// This type implements the [`Prove`] trait.
 ///
-/// # Child process
-///
-/// This [`Prove`] implementation spawns a background process that runs the Stone prover.
+/// # Backend
 ///
-/// ## Security concerns
-///
-/// Because an external process is being spawned and given access to the file system, it is
-/// important to make sure that the *correct* command is being run.
-///
-/// Currently, no `chroot` or other sandboxing mechanism is being used to run the prover, meaning
-/// that if a malicious command is used inadvertedly, it could potentially access or modify the
-/// entire file system.
-///
-/// The environment is cleared to make sure that the prover can't access unnescessary information.
+/// How the prover is actually executed (as a plain child process, inside a sandboxed VM, ...) is
+/// delegated to a [`ProverBackend`] (see [`StoneConfig::backend`]).
 ///
 /// ## File system assumptions
 ///
@@ -106,84 +146,270 @@ impl Default for StoneConfig {
 ///
 /// Note that the prover will write files to the working directory, and it is important to make
 /// sure that those files are not removed while it is running.
-pub struct StoneProver {
+pub struct StoneProver<B = ProcessBackend> {
     /// The working directory in which the Stone prover will be executed.
     working_directory: PathBuf,
-    /// The command that will be spawned every time a proof is requested.
-    command: Command,
+    /// The on-disk format in which [`PROOF_FILE`] is expected to be written.
+    proof_format: ProofFormat,
+    /// Tracks the working directories of jobs spawned by [`StoneProver::prove_job`].
+    jobs: JobRegistry,
+    /// The backend responsible for actually running the prover.
+    backend: B,
+    /// Mirrors [`StoneConfig::require_private_working_dir`].
+    #[cfg(feature = "std")]
+    require_private_working_dir: bool,
+    /// Caches the success of the working-directory permission check; a failed check is not
+    /// cached and runs again on the next call.
+    #[cfg(feature = "std")]
+    permission_check: tokio::sync::OnceCell<()>,
 }
 
-impl StoneProver {
+impl<B> StoneProver<B> {
     /// Creates a new [`StoneProver`] instance from the provided configuration.
-    pub fn new(config: StoneConfig) -> Self {
-        let command = make_command(&config);
-        let working_directory = config.working_directory;
-
+    pub fn new(config: StoneConfig<B>) -> Self {
         Self {
-            working_directory,
-            command,
+            working_directory: config.working_directory,
+            proof_format: config.proof_format,
+            jobs: JobRegistry::default(),
+            backend: config.backend,
+            #[cfg(feature = "std")]
+            require_private_working_dir: config.require_private_working_dir,
+            #[cfg(feature = "std")]
+            permission_check: tokio::sync::OnceCell::new(),
+        }
+    }
+
+    /// Verifies [`StoneConfig::require_private_working_dir`] until the check succeeds once, and
+    /// is a no-op afterwards (or if the option is disabled).
+    #[cfg(feature = "std")]
+    async fn ensure_private_working_dir(&self) -> Result<(), Error> {
+        if !self.require_private_working_dir {
+            return Ok(());
         }
+
+        self.permission_check
+            .get_or_try_init(|| permissions::check_private_directory(&self.working_directory))
+            .await?;
+
+        Ok(())
     }
 }
 
-impl Prove for StoneProver {
+impl<B> StoneProver<B>
+where
+    B: ProverBackend + Clone + Send + Sync + 'static,
+{
+    /// Starts generating a proof for `request` in the background, returning a [`ProveHandle`]
+    /// that can be used to poll its status or cancel it.
+    ///
+    /// Unlike [`Prove::prove`], this does not run the prover in [`working_directory`], but in a
+    /// dedicated subdirectory of it, so that concurrent jobs do not clobber each other's input
+    /// and output files. The subdirectory is removed once the job finishes or is cancelled.
+    ///
+    /// [`working_directory`]: StoneConfig::working_directory
+    pub async fn prove_job(&self, request: &ProofRequest<'_>) -> Result<ProveHandle, Error> {
+        #[cfg(feature = "std")]
+        self.ensure_private_working_dir().await?;
+
+        let status = Arc::new(Mutex::new(JobStatus::Queued));
+
+        let id = self.jobs.alloc();
+        let job_directory = self.working_directory.join("jobs").join(id.to_string());
+        tokio::fs::create_dir_all(&job_directory).await?;
+        self.jobs.track(id, job_directory.clone());
+
+        write_inputs_to_directory(request, &job_directory).await?;
+
+        let (result_tx, result_rx) = oneshot::channel();
+        let backend = self.backend.clone();
+        let proof_format = self.proof_format;
+        let jobs = self.jobs.clone();
+        let task_status = status.clone();
+
+        let join_handle = tokio::spawn(async move {
+            // Only now has the child process actually been (about to be) spawned; until the
+            // runtime gets around to polling this task, `status()` correctly reports `Queued`.
+            // If `cancel()` already moved the status to `Cancelled` before we got here, it also
+            // owns cleanup, so there's nothing left for us to do.
+            if !job::try_transition(&task_status, JobStatus::Queued, JobStatus::Running) {
+                return;
+            }
+
+            let outcome = run_job(&backend, &job_directory, proof_format).await;
+            let new_status = match &outcome {
+                Ok(_) => JobStatus::Succeeded,
+                Err(_) => JobStatus::Failed,
+            };
+
+            // Only the side that wins this transition performs cleanup: if `cancel()` raced us
+            // and already moved the status to `Cancelled`, it already removed the job from the
+            // registry and scheduled the directory deletion, so dropping `result_tx` here (rather
+            // than sending `outcome`) is correct — `wait()` will report `Error::Cancelled`.
+            if job::try_transition(&task_status, JobStatus::Running, new_status) {
+                jobs.remove(id);
+                let _ = tokio::fs::remove_dir_all(&job_directory).await;
+                let _ = result_tx.send(outcome);
+            }
+        });
+
+        Ok(ProveHandle::new(
+            id,
+            status,
+            join_handle.abort_handle(),
+            result_rx,
+            self.jobs.clone(),
+        ))
+    }
+}
+
+/// Runs `backend` against `job_directory` and parses the resulting proof, as used by
+/// [`StoneProver::prove_job`].
+async fn run_job<B: ProverBackend>(
+    backend: &B,
+    job_directory: &Path,
+    proof_format: ProofFormat,
+) -> Result<Proof, Error> {
+    let proof_file = backend.run(job_directory).await?;
+    parse_proof_file(&proof_file, proof_format)
+}
+
+impl<B: ProverBackend> Prove for StoneProver<B> {
     type Err = Error;
 
     async fn prove(&mut self, request: &ProofRequest<'_>) -> Result<Proof, Self::Err> {
-        write_inputs_to_directory(request, &self.working_directory).await?;
+        #[cfg(feature = "std")]
+        self.ensure_private_working_dir().await?;
 
-        let mut child = self.command.spawn()?;
-        let stderr = child.stderr.take().unwrap();
-        let status = child.wait().await?;
+        write_inputs_to_directory(request, &self.working_directory).await?;
+        let proof_file = self.backend.run(&self.working_directory).await?;
+        parse_proof_file(&proof_file, self.proof_format)
+    }
+}
 
-        // If the command has failed, we read the error message from the standard error stream
-        // and return it.
-        if !status.success() {
-            let error_message = error_message(stderr).await;
-            return Err(Error::UnexpectedErrorCode(status, error_message));
+/// Parses the contents of [`PROOF_FILE`] according to `format`, mapping any malformed output to
+/// [`Error::Serde`].
+fn parse_proof_file(contents: &[u8], format: ProofFormat) -> Result<Proof, Error> {
+    match format {
+        ProofFormat::StoneV1 => {
+            let raw: stone_v1::ProofFile = serde_json::from_slice(contents)?;
+            raw.try_into()
         }
+    }
+}
 
-        // TODO: Parse `PROOF_FILE`
+/// Deserialization types matching the `proof_file.json` schema produced by
+/// [`ProofFormat::StoneV1`].
+mod stone_v1 {
+    use super::*;
+    use serde::{Deserialize, Deserializer};
+    use starknet_prove_core::Layout;
+    use std::str::FromStr;
 
-        Ok(Proof {})
+    #[derive(Deserialize)]
+    pub(super) struct ProofFile {
+        #[serde(deserialize_with = "deserialize_hex")]
+        proof_hex: Vec<u8>,
+        public_input: PublicInput,
     }
-}
 
-/// Returns the [`Command`] that will be used by [`StoneProver`] to spawn the process
-/// responsible for generating proofs.
-fn make_command(config: &StoneConfig) -> Command {
-    let mut command = Command::new(&config.command);
-
-    command
-        .current_dir(&config.working_directory)
-        .env_clear() // cleared for security
-        .arg("--out_file")
-        .arg(PROOF_FILE)
-        .arg("--private_input_file")
-        .arg(PRIVATE_INPUT_FILE)
-        .arg("--public_input_file")
-        .arg(PUBLIC_INPUT_FILE)
-        .arg("--prover-config-file")
-        .arg(PROVER_CONFIG_FILE)
-        .arg("--parameter_file")
-        .arg(PARAMETER_FILE)
-        .stdout(Stdio::null())
-        .stdin(Stdio::null())
-        .stderr(Stdio::piped()) // stderr needs to be piped to capture error messages
-        .kill_on_drop(true); // ensures that any error occuring before the child is waited on kills it
-
-    command
-}
+    #[derive(Deserialize)]
+    pub(super) struct PublicInput {
+        layout: String,
+        rc_min: isize,
+        rc_max: isize,
+        n_steps: usize,
+        memory_segments: BTreeMap<String, SegmentBounds>,
+        public_memory: Vec<MemoryEntry>,
+    }
+
+    #[derive(Deserialize)]
+    pub(super) struct SegmentBounds {
+        begin_addr: starknet_types_core::felt::Felt,
+        stop_ptr: starknet_types_core::felt::Felt,
+    }
 
-/// Gets the error message stored in the standard error stream of a child process.
-async fn error_message(mut stderr: ChildStderr) -> String {
-    let mut buf = Vec::new();
-    match stderr.read_to_end(&mut buf).await {
-        Ok(_) => (),
-        Err(_) => return "<failed to read error message>".into(),
+    #[derive(Deserialize)]
+    pub(super) struct MemoryEntry {
+        address: usize,
+        value: starknet_types_core::felt::Felt,
     }
-    match String::from_utf8(buf) {
-        Ok(s) => s,
-        Err(_) => "<error message is not valid UTF-8>".into(),
+
+    impl TryFrom<ProofFile> for Proof {
+        type Error = Error;
+
+        fn try_from(raw: ProofFile) -> Result<Self, Self::Error> {
+            let layout = Layout::from_str(&raw.public_input.layout).map_err(|_| {
+                <serde_json::Error as serde::de::Error>::custom(format!(
+                    "unknown layout {:?}",
+                    raw.public_input.layout
+                ))
+            })?;
+
+            let output_bounds = raw.public_input.memory_segments.get("output");
+            let output = output_bounds
+                .map(|bounds| {
+                    let mut entries: Vec<&MemoryEntry> = raw
+                        .public_input
+                        .public_memory
+                        .iter()
+                        .filter(|entry| {
+                            let address = starknet_types_core::felt::Felt::from(entry.address);
+                            address >= bounds.begin_addr && address < bounds.stop_ptr
+                        })
+                        .collect();
+                    entries.sort_by_key(|entry| entry.address);
+                    entries.into_iter().map(|entry| entry.value).collect()
+                })
+                .unwrap_or_default();
+
+            let memory_segments = raw
+                .public_input
+                .memory_segments
+                .into_iter()
+                .map(|(name, bounds)| MemorySegmentBounds {
+                    name,
+                    start: bounds.begin_addr,
+                    end: bounds.stop_ptr,
+                })
+                .collect();
+
+            Ok(Proof {
+                proof: raw.proof_hex,
+                public_input: ProvenPublicInput {
+                    layout,
+                    rc_min: raw.public_input.rc_min,
+                    rc_max: raw.public_input.rc_max,
+                    n_steps: raw.public_input.n_steps,
+                    memory_segments,
+                    output,
+                },
+            })
+        }
+    }
+
+    /// Decodes a `0x`-prefixed (or bare) hex string into its raw bytes.
+    fn deserialize_hex<'de, D>(deserializer: D) -> Result<Vec<u8>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = <&str>::deserialize(deserializer)?;
+        let s = s.strip_prefix("0x").unwrap_or(s);
+
+        if !s.is_ascii() {
+            return Err(serde::de::Error::custom(
+                "hex-encoded proof contains non-ASCII characters",
+            ));
+        }
+
+        if s.len() % 2 != 0 {
+            return Err(serde::de::Error::custom(
+                "hex-encoded proof has an odd number of digits",
+            ));
+        }
+
+        (0..s.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&s[i..i + 2], 16).map_err(serde::de::Error::custom))
+            .collect()
     }
 }
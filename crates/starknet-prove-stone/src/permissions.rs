@@ -0,0 +1,61 @@
+//! Verifies that a working directory cannot be tampered with by another local user, per
+//! [`StoneConfig::require_private_working_dir`].
+//!
+//! [`StoneConfig::require_private_working_dir`]: crate::StoneConfig::require_private_working_dir
+
+use std::path::Path;
+
+use crate::Error;
+
+/// Checks that `path` and all of its ancestors are owned by the current user (or by root) and
+/// are not group- or world-writable.
+pub(crate) async fn check_private_directory(path: &Path) -> Result<(), Error> {
+    let path = path.to_path_buf();
+    tokio::task::spawn_blocking(move || check_private_directory_blocking(&path))
+        .await
+        .expect("the permission-check task panicked")
+}
+
+#[cfg(unix)]
+fn check_private_directory_blocking(path: &Path) -> Result<(), Error> {
+    use std::os::unix::fs::{MetadataExt, PermissionsExt};
+
+    let path = path.canonicalize()?;
+    let current_uid = unsafe { libc::geteuid() };
+
+    // Group- or world-writable (the owner's write bit is fine, since that's us).
+    const WRITABLE_BY_OTHERS: u32 = 0o022;
+
+    for ancestor in path.ancestors() {
+        let metadata = std::fs::metadata(ancestor)?;
+        let mode = metadata.permissions().mode();
+
+        if mode & WRITABLE_BY_OTHERS != 0 {
+            return Err(Error::InsecurePermissions {
+                path: ancestor.to_path_buf(),
+                mode,
+            });
+        }
+
+        // Ancestors owned by root are trusted as long as they aren't group/world-writable:
+        // only root can tamper with their contents, and nearly every real deployment has a
+        // root-owned `/`, `/home`, etc. above the working directory. Anything owned by
+        // someone else (including us further up the tree, past a root-owned directory) must
+        // still match our uid exactly.
+        if metadata.uid() != current_uid && metadata.uid() != 0 {
+            return Err(Error::InsecurePermissions {
+                path: ancestor.to_path_buf(),
+                mode,
+            });
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn check_private_directory_blocking(_path: &Path) -> Result<(), Error> {
+    // Group/world-writable bits are a POSIX concept; there is nothing meaningful to check on
+    // other platforms.
+    Ok(())
+}